@@ -0,0 +1,49 @@
+//! Single-use freshness nonces for the attestation flow.
+//!
+//! Adapted from the ACME challenge/nonce model: `GET`/`POST /challenge`
+//! hands out a random nonce that the client must fold into its
+//! attestation evidence's `report_data` before calling `/attest`. Each
+//! nonce is valid for `TTL` and can be consumed at most once, so a
+//! captured report/quote can't be replayed against a fresh CSR.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rand::RngCore;
+
+/// Byte length of an issued nonce.
+pub const NONCE_SIZE: usize = 32;
+
+/// How long an issued nonce remains valid if unused.
+const TTL: Duration = Duration::from_secs(300);
+
+/// Outstanding nonces, keyed by value, each mapped to its expiry.
+#[derive(Debug, Default)]
+pub struct Challenges(Mutex<HashMap<[u8; NONCE_SIZE], Instant>>);
+
+impl Challenges {
+    /// Issue a fresh random nonce, valid for `TTL`.
+    pub fn issue(&self) -> [u8; NONCE_SIZE] {
+        let mut nonce = [0u8; NONCE_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let mut outstanding = self.0.lock().unwrap();
+        // Sweep nonces that expired without ever being consumed, so
+        // hammering this unauthenticated endpoint can't grow the map
+        // without bound.
+        let now = Instant::now();
+        outstanding.retain(|_, expires| *expires > now);
+        outstanding.insert(nonce, now + TTL);
+        nonce
+    }
+
+    /// Consume `nonce` if it is outstanding and unexpired. Either way,
+    /// once looked up it can't be presented again.
+    pub fn consume(&self, nonce: &[u8; NONCE_SIZE]) -> bool {
+        match self.0.lock().unwrap().remove(nonce) {
+            Some(expires) => Instant::now() <= expires,
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! A TEE-agnostic view over attestation evidence.
+//!
+//! [`Evidence`] auto-detects and wraps a parsed AMD SEV-SNP report or
+//! Intel SGX DCAP quote, and [`Verifier`] gives both a uniform
+//! interface so callers like the `attest` handler don't need to
+//! special-case each backend. Adding a new TEE (e.g. TDX) is a matter
+//! of adding an `Evidence` variant and implementing `Verifier` for it.
+
+use crate::ext::sgx::quote::Quote;
+use crate::snp::{self, SnpReportData};
+
+/// The trust anchors needed to verify each evidence backend's
+/// signature chain. Each backend only reads the fields relevant to it.
+#[derive(Debug, Clone, Copy)]
+pub struct TrustAnchors<'a> {
+    /// PEM-encoded AMD Root Key, the SNP trust anchor.
+    pub ark_pem: &'a str,
+    /// PEM-encoded AMD SEV Key, the SNP intermediate.
+    pub ask_pem: &'a str,
+    /// DER-encoded VCEK leaf certificate covering the report being
+    /// verified.
+    pub vcek_der: &'a [u8],
+    /// PEM-encoded Intel SGX Root CA, the DCAP trust anchor.
+    pub sgx_root_ca_pem: &'a str,
+}
+
+/// The claims common to every evidence backend, once its signature
+/// chain has been verified. A policy decision (is this measurement/TCB
+/// allowlisted?) is made against these, not against backend-specific
+/// types.
+#[derive(Debug, Clone)]
+pub struct VerifiedClaims {
+    /// The attested code identity: the SNP `measurement`, or the SGX
+    /// `mr_enclave || mr_signer`.
+    pub measurement: Vec<u8>,
+    /// The attested TCB/patch level: the SNP `reported_tcb`, or the SGX
+    /// `isv_svn`.
+    pub tcb: u64,
+    /// The report's freshness/binding payload.
+    pub report_data: Vec<u8>,
+}
+
+/// A uniform interface over a backend's parsed attestation evidence.
+pub trait Verifier {
+    /// Verify this evidence's signature chain against `anchors` and
+    /// return its claims. Errs if the chain doesn't verify.
+    fn verify_signature(&self, anchors: &TrustAnchors<'_>) -> anyhow::Result<VerifiedClaims>;
+
+    /// This evidence's `report_data`/freshness payload. Available even
+    /// before [`Verifier::verify_signature`] succeeds, since callers
+    /// still need it to check binding to a fresh nonce and CSR key
+    /// before spending a cryptographic chain verification on it.
+    fn report_data(&self) -> &[u8];
+}
+
+impl Verifier for SnpReportData {
+    fn verify_signature(&self, anchors: &TrustAnchors<'_>) -> anyhow::Result<VerifiedClaims> {
+        let now = std::time::SystemTime::now();
+        snp::verify_snp_chain(self, anchors.vcek_der, anchors.ark_pem, anchors.ask_pem, now)?;
+        Ok(VerifiedClaims {
+            measurement: self.measurement.to_vec(),
+            tcb: self.reported_tcb,
+            report_data: self.report_data.to_vec(),
+        })
+    }
+
+    fn report_data(&self) -> &[u8] {
+        &self.report_data
+    }
+}
+
+impl<'a> Verifier for Quote<'a> {
+    fn verify_signature(&self, anchors: &TrustAnchors<'_>) -> anyhow::Result<VerifiedClaims> {
+        let now = std::time::SystemTime::now();
+        let report = self.verify(anchors.sgx_root_ca_pem, now)?;
+        let mut measurement = report.mr_enclave.to_vec();
+        measurement.extend_from_slice(&report.mr_signer);
+        Ok(VerifiedClaims {
+            measurement,
+            tcb: report.isv_svn as u64,
+            report_data: report.report_data.to_vec(),
+        })
+    }
+
+    fn report_data(&self) -> &[u8] {
+        &self.report_body().report_data
+    }
+}
+
+/// Auto-detected attestation evidence, extensible to other TEE
+/// backends as they're added.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Evidence<'a> {
+    Snp(SnpReportData),
+    Sgx(Quote<'a>),
+}
+
+impl<'a> TryFrom<&'a [u8]> for Evidence<'a> {
+    type Error = anyhow::Error;
+
+    /// An SNP report is exactly `snp::REPORT_SIZE` bytes; anything else
+    /// is tried as an SGX quote, whose header names its version and
+    /// whose signature-data length field gives the rest of its size.
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        if bytes.len() == snp::REPORT_SIZE {
+            Ok(Evidence::Snp(SnpReportData::try_from(bytes)?))
+        } else {
+            Ok(Evidence::Sgx(Quote::try_from(bytes)?))
+        }
+    }
+}
+
+impl<'a> Verifier for Evidence<'a> {
+    fn verify_signature(&self, anchors: &TrustAnchors<'_>) -> anyhow::Result<VerifiedClaims> {
+        match self {
+            Evidence::Snp(report) => report.verify_signature(anchors),
+            Evidence::Sgx(quote) => quote.verify_signature(anchors),
+        }
+    }
+
+    fn report_data(&self) -> &[u8] {
+        match self {
+            Evidence::Snp(report) => report.report_data(),
+            Evidence::Sgx(quote) => quote.report_data(),
+        }
+    }
+}
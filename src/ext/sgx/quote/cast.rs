@@ -0,0 +1,52 @@
+use sgx::ReportBody;
+
+use super::error::QuoteError;
+use super::header::QuoteHeader;
+use super::sizes::{QUOTE_HEADER_SIZE, REPORT_SIZE};
+
+/// Reinterpret a byte slice as a fixed-size array reference, or fail
+/// with a field name attached for a useful error message.
+pub fn slice_cast<'a, const N: usize>(
+    field: &'static str,
+    bytes: &'a [u8],
+) -> Result<&'a [u8; N], QuoteError> {
+    bytes.try_into().map_err(|_| QuoteError::WrongSize {
+        field,
+        expected: N,
+        got: bytes.len(),
+    })
+}
+
+/// Slice `bytes[offset..offset + len]`, failing instead of panicking
+/// when `offset`/`len` (often attacker-controlled, e.g. a length field
+/// read from the quote itself) run past the end of `bytes`.
+pub fn checked_slice<'a>(
+    field: &'static str,
+    bytes: &'a [u8],
+    offset: usize,
+    len: usize,
+) -> Result<&'a [u8], QuoteError> {
+    let wrong_size = || QuoteError::WrongSize {
+        field,
+        expected: len,
+        got: bytes.len().saturating_sub(offset),
+    };
+    let end = offset.checked_add(len).ok_or_else(wrong_size)?;
+    bytes.get(offset..end).ok_or_else(wrong_size)
+}
+
+/// `ReportBody` is `repr(C)` over exactly `REPORT_SIZE` bytes; both
+/// directions of this cast are plain reinterpretations, no parsing.
+pub fn report_body_from_bytes(bytes: [u8; REPORT_SIZE]) -> ReportBody {
+    unsafe { std::mem::transmute(bytes) }
+}
+
+pub fn report_body_to_bytes(report: &ReportBody) -> [u8; REPORT_SIZE] {
+    unsafe { std::mem::transmute_copy(report) }
+}
+
+/// `QuoteHeader` is `repr(C)` over exactly `QUOTE_HEADER_SIZE` bytes;
+/// this cast is a plain reinterpretation, no parsing.
+pub fn quote_header_to_bytes(header: &QuoteHeader) -> [u8; QUOTE_HEADER_SIZE] {
+    unsafe { std::mem::transmute_copy(header) }
+}
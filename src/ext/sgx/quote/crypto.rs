@@ -0,0 +1,150 @@
+use std::time::SystemTime;
+
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+
+use der::Decodable;
+use x509::Certificate;
+
+use crate::crypto::PkiPath;
+
+/// Raw (r || s) ECDSA-P256 signature, as embedded in quotes: a 32-byte
+/// big-endian `r` followed by a 32-byte big-endian `s`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct EcdsaP256Sig {
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+}
+
+/// Raw uncompressed `(x || y)` EC point for an ECDSA-P256 public key.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct EcdsaPubKey {
+    pub x: [u8; 32],
+    pub y: [u8; 32],
+}
+
+impl From<[u8; 64]> for EcdsaP256Sig {
+    fn from(bytes: [u8; 64]) -> Self {
+        unsafe { std::mem::transmute(bytes) }
+    }
+}
+
+impl From<[u8; 64]> for EcdsaPubKey {
+    fn from(bytes: [u8; 64]) -> Self {
+        unsafe { std::mem::transmute(bytes) }
+    }
+}
+
+impl EcdsaP256Sig {
+    fn to_signature(self) -> Result<Signature, anyhow::Error> {
+        let mut raw = [0u8; 64];
+        raw[..32].copy_from_slice(&self.r);
+        raw[32..].copy_from_slice(&self.s);
+        Ok(Signature::try_from(raw.as_slice())?)
+    }
+}
+
+impl EcdsaPubKey {
+    fn to_verifying_key(self) -> Result<VerifyingKey, anyhow::Error> {
+        // SEC1 uncompressed point encoding: 0x04 || x || y.
+        let mut point = [0u8; 65];
+        point[0] = 0x04;
+        point[1..33].copy_from_slice(&self.x);
+        point[33..].copy_from_slice(&self.y);
+        Ok(VerifyingKey::from_sec1_bytes(&point)?)
+    }
+
+    /// The raw `(x || y)` bytes, as used in the QE report data hash.
+    pub fn as_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.x);
+        bytes[32..].copy_from_slice(&self.y);
+        bytes
+    }
+}
+
+/// Verify a raw ECDSA-P256-SHA256 signature over `message`.
+pub fn verify_p256_sha256(
+    pubkey: &EcdsaPubKey,
+    message: &[u8],
+    sig: &EcdsaP256Sig,
+) -> Result<(), anyhow::Error> {
+    let key = pubkey.to_verifying_key()?;
+    let sig = sig.to_signature()?;
+    key.verify(message, &sig)?;
+    Ok(())
+}
+
+/// Extract the ECDSA-P256 public key from the leaf (first) certificate
+/// of a PEM-encoded PCK certification chain.
+pub fn pck_leaf_key(chain_pem: &[u8]) -> Result<EcdsaPubKey, anyhow::Error> {
+    let pem = std::str::from_utf8(chain_pem)?;
+    let ders = PkiPath::parse_pem(pem)?;
+    let leaf_der = ders
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("empty PCK certification chain"))?;
+    let leaf = Certificate::from_der(leaf_der)?;
+
+    let point = leaf
+        .tbs_certificate
+        .subject_public_key_info
+        .subject_public_key;
+    anyhow::ensure!(
+        point.len() == 65 && point[0] == 0x04,
+        "PCK leaf key is not an uncompressed P-256 point"
+    );
+
+    let mut x = [0u8; 32];
+    let mut y = [0u8; 32];
+    x.copy_from_slice(&point[1..33]);
+    y.copy_from_slice(&point[33..65]);
+    Ok(EcdsaPubKey { x, y })
+}
+
+/// Check that `cert`'s validity period covers `now`, so an expired or
+/// not-yet-valid PCK leaf/intermediate/root can't stand in the chain.
+fn verify_validity(cert: &Certificate<'_>, now: SystemTime) -> Result<(), anyhow::Error> {
+    let validity = &cert.tbs_certificate.validity;
+    let not_before = SystemTime::try_from(validity.not_before)?;
+    let not_after = SystemTime::try_from(validity.not_after)?;
+    anyhow::ensure!(now >= not_before, "certificate is not yet valid");
+    anyhow::ensure!(now <= not_after, "certificate has expired");
+    Ok(())
+}
+
+/// Validate the PEM-encoded PCK certification chain up to a trusted
+/// Intel SGX Root CA: each certificate's signature verifies the next,
+/// ending in `root_ca_pem` as the anchor, and every certificate's
+/// validity period covers `now`.
+pub fn verify_pck_chain(
+    chain_pem: &[u8],
+    root_ca_pem: &str,
+    now: SystemTime,
+) -> Result<(), anyhow::Error> {
+    let chain_pem = std::str::from_utf8(chain_pem)?;
+    let chain = PkiPath::from_ders(&PkiPath::parse_pem(chain_pem)?)?;
+    anyhow::ensure!(!chain.is_empty(), "empty PCK certification chain");
+
+    let root = PkiPath::from_ders(&PkiPath::parse_pem(root_ca_pem)?)?;
+    let root = root
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("no Intel SGX Root CA configured"))?;
+
+    verify_validity(root, now)?;
+    for cert in &chain {
+        verify_validity(cert, now)?;
+    }
+
+    // The chain is leaf-first; walk it leaf -> ... -> intermediate,
+    // then confirm the final link is signed by the trust anchor.
+    for pair in chain.windows(2) {
+        let (child, issuer) = (&pair[0], &pair[1]);
+        issuer.tbs_certificate.verify_crt(child)?;
+    }
+
+    let top = chain.last().unwrap();
+    root.tbs_certificate.verify_crt(top)?;
+
+    Ok(())
+}
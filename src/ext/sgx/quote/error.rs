@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Errors produced while parsing or verifying a DCAP quote.
+#[derive(Debug)]
+pub enum QuoteError {
+    /// A fixed-size field did not have the expected length.
+    WrongSize {
+        field: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// The quote header named a version this crate doesn't support.
+    UnsupportedVersion(u16),
+    /// A cryptographic or chain-of-trust check failed.
+    Verification(anyhow::Error),
+}
+
+impl fmt::Display for QuoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongSize {
+                field,
+                expected,
+                got,
+            } => write!(
+                f,
+                "{field} has the wrong size: expected {expected}, got {got}"
+            ),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported quote version {v}"),
+            Self::Verification(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for QuoteError {}
+
+impl From<anyhow::Error> for QuoteError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Verification(e)
+    }
+}
@@ -0,0 +1,41 @@
+use super::error::QuoteError;
+
+/// Section A.4: the 48-byte header common to all quote versions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteHeader {
+    pub version: u16,
+    pub attestation_key_type: u16,
+    pub tee_type: u32,
+    pub qe_svn: u16,
+    pub pce_svn: u16,
+    pub qe_vendor_id: [u8; 16],
+    pub user_data: [u8; 20],
+}
+
+/// The quote body layouts this crate understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteVersion {
+    V3,
+}
+
+impl QuoteHeader {
+    /// Verify fields in the structure.
+    pub fn verify(&self) -> Result<(), anyhow::Error> {
+        self.version()?;
+        Ok(())
+    }
+
+    pub fn version(&self) -> Result<QuoteVersion, QuoteError> {
+        match self.version {
+            3 => Ok(QuoteVersion::V3),
+            v => Err(QuoteError::UnsupportedVersion(v)),
+        }
+    }
+}
+
+impl From<[u8; 48]> for QuoteHeader {
+    fn from(bytes: [u8; 48]) -> Self {
+        unsafe { std::mem::transmute(bytes) }
+    }
+}
@@ -15,8 +15,10 @@ pub mod report;
 pub mod signature;
 mod sizes;
 
+use std::time::SystemTime;
+
 use self::{
-    cast::{report_body_from_bytes, slice_cast},
+    cast::{checked_slice, quote_header_to_bytes, report_body_from_bytes, slice_cast},
     header::{QuoteHeader, QuoteVersion},
     sizes::*,
 };
@@ -36,11 +38,28 @@ pub struct Quote<'a> {
 }
 
 impl<'a> Quote<'a> {
-    /// Verify fields in the structure.
-    pub fn verify(&self) -> Result<(), anyhow::Error> {
+    /// Verify the quote end to end: the header shape, the DCAP ECDSA
+    /// signature chain (QE report -> PCK leaf -> Intel SGX Root CA,
+    /// attestation key -> QE report, ISV report -> attestation key),
+    /// and the ISV report's shape. Returns the verified `ReportBody` so
+    /// the caller can apply its own policy (measurement/key binding).
+    /// `now` is checked against the PCK chain's validity periods.
+    pub fn verify(
+        &self,
+        sgx_root_ca_pem: &str,
+        now: SystemTime,
+    ) -> Result<&ReportBody, anyhow::Error> {
         self.header.verify()?;
-        self.body.verify()?;
-        Ok(())
+        let header_bytes = quote_header_to_bytes(&self.header);
+        self.body.verify(&header_bytes, sgx_root_ca_pem, now)
+    }
+
+    /// The attested enclave's report, parsed but not yet cryptographically
+    /// verified. Use [`Quote::verify`] before trusting its contents.
+    pub fn report_body(&self) -> &ReportBody {
+        match &self.body {
+            QuoteBody::V3(body) => body.report_body(),
+        }
     }
 }
 
@@ -48,8 +67,11 @@ impl<'a> TryFrom<&'a [u8]> for Quote<'a> {
     type Error = QuoteError;
 
     fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
-        let header =
-            (*slice_cast::<QUOTE_HEADER_SIZE>("header", &bytes[0..QUOTE_HEADER_SIZE])?).into();
+        let header = (*slice_cast::<QUOTE_HEADER_SIZE>(
+            "header",
+            checked_slice("header", bytes, 0, QUOTE_HEADER_SIZE)?,
+        )?)
+        .into();
         let body = QuoteBody::try_from((&header, bytes))?;
         Ok(Self { header, body })
     }
@@ -65,10 +87,15 @@ pub enum QuoteBody<'a> {
 }
 
 impl<'a> QuoteBody<'a> {
-    /// Verify fields in the structure.
-    pub fn verify(&self) -> Result<(), anyhow::Error> {
+    /// Verify the DCAP ECDSA chain and return the verified `ReportBody`.
+    pub fn verify(
+        &self,
+        header_bytes: &[u8; QUOTE_HEADER_SIZE],
+        sgx_root_ca_pem: &str,
+        now: SystemTime,
+    ) -> Result<&ReportBody, anyhow::Error> {
         match self {
-            QuoteBody::V3(quote) => quote.verify(),
+            QuoteBody::V3(quote) => quote.verify(header_bytes, sgx_root_ca_pem, now),
         }
     }
 }
@@ -131,11 +158,18 @@ impl<'a> QuoteBodyV3<'a> {
         &self.sig_data
     }
 
-    /// Verify fields in the structure.
-    pub fn verify(&self) -> Result<(), anyhow::Error> {
+    /// Verify the DCAP ECDSA chain over this quote body and return the
+    /// verified `ReportBody`.
+    pub fn verify(
+        &self,
+        header_bytes: &[u8; QUOTE_HEADER_SIZE],
+        sgx_root_ca_pem: &str,
+        now: SystemTime,
+    ) -> Result<&ReportBody, anyhow::Error> {
         report::quote_report_body_verify(self.report_body())?;
-        self.sig_data().verify()?;
-        Ok(())
+        self.sig_data()
+            .verify(header_bytes, self.report_body(), sgx_root_ca_pem, now)?;
+        Ok(self.report_body())
     }
 }
 
@@ -145,15 +179,24 @@ impl<'a> TryFrom<&'a [u8]> for QuoteBodyV3<'a> {
     fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
         let report_body = *slice_cast::<REPORT_SIZE>(
             "isv enclave report",
-            &bytes[QUOTE_HEADER_SIZE..(QUOTE_HEADER_SIZE + REPORT_SIZE)],
+            checked_slice("isv enclave report", bytes, QUOTE_HEADER_SIZE, REPORT_SIZE)?,
         )?;
         let report_body = report_body_from_bytes(report_body);
         let sig_data_len = u32::from_le_bytes(*slice_cast::<U32_SIZE>(
             "sig data len",
-            &bytes[QUOTE_SIG_START - QUOTE_SIG_DATA_LEN_SIZE..QUOTE_SIG_START],
+            checked_slice(
+                "sig data len",
+                bytes,
+                QUOTE_SIG_START - QUOTE_SIG_DATA_LEN_SIZE,
+                QUOTE_SIG_DATA_LEN_SIZE,
+            )?,
         )?);
-        let expected_quote_len = QUOTE_SIG_START + sig_data_len as usize;
-        let sig_data = SigData::try_from(&bytes[QUOTE_SIG_START..expected_quote_len])?;
+        let sig_data = SigData::try_from(checked_slice(
+            "sig data",
+            bytes,
+            QUOTE_SIG_START,
+            sig_data_len as usize,
+        )?)?;
 
         Ok(Self {
             report_body,
@@ -0,0 +1,145 @@
+use std::time::SystemTime;
+
+use sgx::ReportBody;
+use sha2::{Digest, Sha256};
+
+use super::cast::{checked_slice, report_body_from_bytes, report_body_to_bytes, slice_cast};
+use super::crypto::{pck_leaf_key, verify_p256_sha256, verify_pck_chain, EcdsaP256Sig, EcdsaPubKey};
+use super::error::QuoteError;
+use super::sizes::{PUB_KEY_SIZE, REPORT_SIZE, SIG_SIZE, U16_SIZE, U32_SIZE};
+
+/// Offset of `ReportData` within an ISV/QE enclave report. Section A.4.
+const REPORT_DATA_OFFSET: usize = 320;
+const REPORT_DATA_SIZE: usize = 32;
+
+/// DCAP ECDSA quote signature data, Section A.4:
+/// ISV report sig (64) || AK pub (64) || QE report (384) ||
+/// QE report sig (64) || QE auth data (2-byte len + data) ||
+/// QE cert data (2-byte type + 4-byte len + data).
+#[derive(Debug, Clone)]
+pub struct SigData<'a> {
+    isv_report_sig: EcdsaP256Sig,
+    attestation_key: EcdsaPubKey,
+    qe_report: ReportBody,
+    qe_report_sig: EcdsaP256Sig,
+    qe_auth_data: &'a [u8],
+    /// The QE certification data payload. In practice this is almost
+    /// always cert type 5: a PEM-encoded PCK certification chain.
+    pck_cert_chain: &'a [u8],
+}
+
+impl<'a> SigData<'a> {
+    /// The attestation key that signs the quote header and ISV
+    /// enclave report.
+    #[allow(unused)]
+    pub fn attestation_key(&self) -> &EcdsaPubKey {
+        &self.attestation_key
+    }
+
+    /// Verify the DCAP ECDSA chain end to end:
+    /// 1. the QE report is signed by the PCK leaf cert (ECDSA-P256-SHA256);
+    /// 2. SHA-256(attestation key || QE auth data) matches the QE
+    ///    report's `ReportData` (first 32 bytes);
+    /// 3. `header_bytes || isv_report` is signed by the attestation key;
+    /// 4. the PCK certification chain validates up to the Intel SGX
+    ///    Root CA, with every certificate's validity period covering
+    ///    `now`.
+    pub fn verify(
+        &self,
+        header_bytes: &[u8],
+        isv_report: &ReportBody,
+        sgx_root_ca_pem: &str,
+        now: SystemTime,
+    ) -> Result<(), anyhow::Error> {
+        let pck_leaf = pck_leaf_key(self.pck_cert_chain)?;
+        let qe_report_bytes = report_body_to_bytes(&self.qe_report);
+
+        // 1. The QE report is signed by the PCK leaf certificate.
+        verify_p256_sha256(&pck_leaf, &qe_report_bytes, &self.qe_report_sig)?;
+
+        // 2. The QE attests to the attestation key and auth data.
+        let mut hasher = Sha256::new();
+        hasher.update(self.attestation_key.as_bytes());
+        hasher.update(self.qe_auth_data);
+        let expected = hasher.finalize();
+        let report_data =
+            &qe_report_bytes[REPORT_DATA_OFFSET..REPORT_DATA_OFFSET + REPORT_DATA_SIZE];
+        anyhow::ensure!(
+            report_data == expected.as_slice(),
+            "QE report does not attest to the attestation key"
+        );
+
+        // 3. The quote header and ISV enclave report are signed by the
+        // attestation key.
+        let isv_report_bytes = report_body_to_bytes(isv_report);
+        let mut signed = Vec::with_capacity(header_bytes.len() + isv_report_bytes.len());
+        signed.extend_from_slice(header_bytes);
+        signed.extend_from_slice(&isv_report_bytes);
+        verify_p256_sha256(&self.attestation_key, &signed, &self.isv_report_sig)?;
+
+        // 4. The PCK cert chain is rooted in a trusted Intel SGX Root CA.
+        verify_pck_chain(self.pck_cert_chain, sgx_root_ca_pem, now)?;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigData<'a> {
+    type Error = QuoteError;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let mut offset = 0;
+
+        let isv_report_sig = *slice_cast::<SIG_SIZE>(
+            "isv report sig",
+            checked_slice("isv report sig", bytes, offset, SIG_SIZE)?,
+        )?;
+        offset += SIG_SIZE;
+
+        let attestation_key = *slice_cast::<PUB_KEY_SIZE>(
+            "attestation key",
+            checked_slice("attestation key", bytes, offset, PUB_KEY_SIZE)?,
+        )?;
+        offset += PUB_KEY_SIZE;
+
+        let qe_report = *slice_cast::<REPORT_SIZE>(
+            "qe report",
+            checked_slice("qe report", bytes, offset, REPORT_SIZE)?,
+        )?;
+        offset += REPORT_SIZE;
+
+        let qe_report_sig = *slice_cast::<SIG_SIZE>(
+            "qe report sig",
+            checked_slice("qe report sig", bytes, offset, SIG_SIZE)?,
+        )?;
+        offset += SIG_SIZE;
+
+        let qe_auth_len = u16::from_le_bytes(*slice_cast::<U16_SIZE>(
+            "qe auth data len",
+            checked_slice("qe auth data len", bytes, offset, U16_SIZE)?,
+        )?);
+        offset += U16_SIZE;
+        let qe_auth_data = checked_slice("qe auth data", bytes, offset, qe_auth_len as usize)?;
+        offset += qe_auth_len as usize;
+
+        // Skip the 2-byte certification data type; this crate only
+        // supports type 5 (a PEM PCK certification chain).
+        offset += U16_SIZE;
+        let cert_data_len = u32::from_le_bytes(*slice_cast::<U32_SIZE>(
+            "cert data len",
+            checked_slice("cert data len", bytes, offset, U32_SIZE)?,
+        )?);
+        offset += U32_SIZE;
+        let pck_cert_chain =
+            checked_slice("pck cert chain", bytes, offset, cert_data_len as usize)?;
+
+        Ok(Self {
+            isv_report_sig: (*isv_report_sig).into(),
+            attestation_key: (*attestation_key).into(),
+            qe_report: report_body_from_bytes(*qe_report),
+            qe_report_sig: (*qe_report_sig).into(),
+            qe_auth_data,
+            pck_cert_chain,
+        })
+    }
+}
@@ -1,8 +1,17 @@
 extern crate core;
 
+mod challenge;
 mod crypto;
+mod evidence;
+mod ext;
+mod policy;
+mod snp;
 
+use challenge::{Challenges, NONCE_SIZE};
 use crypto::*;
+use evidence::{Evidence, TrustAnchors, VerifiedClaims, Verifier};
+use policy::Policy;
+use snp::SnpReportData;
 use x509::request::CertReq;
 
 use std::net::SocketAddr;
@@ -14,15 +23,18 @@ use std::time::{Duration, SystemTime};
 use axum::body::Bytes;
 use axum::extract::{Extension, TypedHeader};
 use axum::headers::ContentType;
-use axum::routing::post;
+use axum::routing::{get, post};
 use axum::{AddExtensionLayer, Router};
-use der::asn1::UIntBytes;
+use der::asn1::{OctetString, UIntBytes, Utf8String};
 use der::{Encodable, Sequence};
 use hyper::StatusCode;
 use mime::Mime;
+use sha2::{Digest, Sha256};
 
 use der::Decodable;
 use pkcs8::PrivateKeyInfo;
+use x509::ext::Extension as X509Extension;
+use x509::request::CertReqInfo;
 use x509::time::{Time, Validity};
 use x509::{Certificate, TbsCertificate};
 
@@ -31,6 +43,83 @@ use zeroize::Zeroizing;
 
 const PKCS10: &str = "application/pkcs10";
 
+/// Private-arc OID identifying the CSR attribute that carries raw
+/// attestation evidence (an SNP report or an SGX quote).
+const OID_ATTESTATION_EVIDENCE: &str = "1.3.6.1.4.1.57264.1.1";
+
+/// Private-arc OID identifying the CSR attribute that carries the
+/// DER-encoded VCEK certificate covering this request's SNP report, the
+/// per-chip leaf the server has no other way to obtain. Unused (and may
+/// be absent) for SGX evidence, whose PCK certification chain already
+/// travels inside the quote's signature data.
+const OID_ATTESTATION_CERT_CHAIN: &str = "1.3.6.1.4.1.57264.1.3";
+
+use snp::{REPORT_DATA_OFFSET as SNP_REPORT_DATA_OFFSET, REPORT_SIZE as SNP_REPORT_SIZE};
+
+/// Pull the first value of the CSR attribute identified by `oid`, as a
+/// raw octet string.
+fn csr_attribute<'a>(cr: &'a CertReqInfo<'_>, oid: &str) -> Option<&'a [u8]> {
+    let oid = oid.parse().ok()?;
+    cr.attributes.iter().find(|attr| attr.oid == oid).and_then(|attr| {
+        attr.values
+            .iter()
+            .next()
+            .and_then(|any| OctetString::try_from(any.clone()).ok())
+            .map(|os| os.as_bytes())
+    })
+}
+
+/// Pull the raw attestation evidence blob out of a verified CSR, if the
+/// requester attached one via [`OID_ATTESTATION_EVIDENCE`].
+fn attestation_evidence<'a>(cr: &'a CertReqInfo<'_>) -> Option<&'a [u8]> {
+    csr_attribute(cr, OID_ATTESTATION_EVIDENCE)
+}
+
+/// Pull the DER-encoded VCEK certificate out of a verified CSR, if the
+/// requester attached one via [`OID_ATTESTATION_CERT_CHAIN`].
+fn attestation_vcek<'a>(cr: &'a CertReqInfo<'_>) -> Option<&'a [u8]> {
+    csr_attribute(cr, OID_ATTESTATION_CERT_CHAIN)
+}
+
+/// Digest the DER-encoded SPKI so it can be bound into `report_data`.
+///
+/// SHA-256, not the SHA-384 an SNP report's 64-byte `report_data` could
+/// fit: the other 32 bytes carry the `/challenge` nonce (see
+/// [`verify_report_data_binding`]), and a single digest width has to
+/// cover both backends' 64-byte `report_data` uniformly.
+fn public_key_digest(cr: &CertReqInfo<'_>) -> Option<[u8; 32]> {
+    let spki = cr.public_key.to_vec().ok()?;
+    Some(Sha256::digest(&spki).into())
+}
+
+/// Check that `evidence`'s `report_data` binds the CSR's public key and
+/// a fresh, unconsumed `/challenge` nonce. `report_data` must lay out
+/// the SHA-256 public-key digest in its first 32 bytes and the nonce in
+/// the next 32, regardless of which backend produced `evidence`.
+fn verify_report_data_binding(
+    cr: &CertReqInfo<'_>,
+    evidence: &Evidence,
+    challenges: &Challenges,
+) -> Result<(), StatusCode> {
+    let report_data = evidence.report_data();
+    if report_data.len() < 64 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let (digest, nonce) = report_data[..64].split_at(32);
+
+    let expected = public_key_digest(cr).ok_or(StatusCode::BAD_REQUEST)?;
+    if digest != expected.as_slice() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let nonce: [u8; NONCE_SIZE] = nonce.try_into().or(Err(StatusCode::BAD_REQUEST))?;
+    if !challenges.consume(&nonce) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Debug, Parser)]
 struct Args {
     #[clap(short, long)]
@@ -38,6 +127,24 @@ struct Args {
 
     #[clap(short, long)]
     crt: PathBuf,
+
+    /// PEM-encoded AMD Root Key, the trust anchor for the SNP chain.
+    #[clap(long)]
+    ark: PathBuf,
+
+    /// PEM-encoded AMD SEV Key, the intermediate that signs VCEKs.
+    #[clap(long)]
+    ask: PathBuf,
+
+    /// PEM-encoded Intel SGX Root CA, the trust anchor for the DCAP PCK
+    /// certification chain embedded in a submitted quote.
+    #[clap(long)]
+    sgx_root_ca: PathBuf,
+
+    /// TOML file listing the enclave/guest identities this server will
+    /// issue a certificate for.
+    #[clap(long)]
+    policy: PathBuf,
 }
 
 impl Args {
@@ -45,6 +152,11 @@ impl Args {
         Ok(State {
             key: std::fs::read(self.key)?.into(),
             crt: std::fs::read(self.crt)?,
+            ark: std::fs::read_to_string(self.ark)?,
+            ask: std::fs::read_to_string(self.ask)?,
+            sgx_root_ca: std::fs::read_to_string(self.sgx_root_ca)?,
+            policy: Policy::load(&self.policy)?,
+            challenges: Challenges::default(),
             ord: AtomicUsize::default(),
         })
     }
@@ -54,6 +166,11 @@ impl Args {
 struct State {
     key: Zeroizing<Vec<u8>>,
     crt: Vec<u8>,
+    ark: String,
+    ask: String,
+    sgx_root_ca: String,
+    policy: Policy,
+    challenges: Challenges,
     ord: AtomicUsize,
 }
 
@@ -67,59 +184,80 @@ struct EcdsaSig<'a> {
     s: UIntBytes<'a>,
 }
 
-#[repr(C, packed)]
-#[derive(Debug, Copy, Clone)]
-struct SnpReportData {
-    pub version: u32,
-    pub guest_svn: u32,
-    pub policy: u64,
-    pub family_id: [u8; 16],
-    pub image_id: [u8; 16],
-    pub vmpl: u32,
-    pub sig_algo: u32,
-    pub plat_version: u64,
-    pub plat_info: u64,
-    pub author_key_en: u32,
-    rsvd1: u32,
-    pub report_data: [u8; 64],
-    pub measurement: [u8; 48],
-    pub host_data: [u8; 32],
-    pub id_key_digest: [u8; 48],
-    pub author_key_digest: [u8; 48],
-    pub report_id: [u8; 32],
-    pub report_id_ma: [u8; 32],
-    pub reported_tcb: u64,
-    rsvd2: [u8; 24],
-    pub chip_id: [u8; 64],
-    rsvd3: [u8; 192],
-    pub signature: [u8; 512],
+const OID_BASIC_CONSTRAINTS: &str = "2.5.29.19";
+const OID_KEY_USAGE: &str = "2.5.29.15";
+const OID_SUBJECT_ALT_NAME: &str = "2.5.29.17";
+
+/// Private-arc OID for the non-critical extension carrying the verified
+/// attestation evidence, sibling of [`OID_ATTESTATION_EVIDENCE`] under
+/// the same enterprise arc.
+const OID_ATTESTATION_RESULT: &str = "1.3.6.1.4.1.57264.1.2";
+
+/// `BasicConstraints ::= SEQUENCE { cA BOOLEAN DEFAULT FALSE }`, DER
+/// encoded. `cA` is omitted since it's the default value, leaving an
+/// empty SEQUENCE.
+const BASIC_CONSTRAINTS_CA_FALSE: &[u8] = &[0x30, 0x00];
+
+/// `KeyUsage ::= BIT STRING`, DER encoded with only `digitalSignature`
+/// (bit 0) set.
+const KEY_USAGE_DIGITAL_SIGNATURE: &[u8] = &[0x03, 0x02, 0x07, 0x80];
+
+/// AttestationResult ::= SEQUENCE {
+///     kind  UTF8String,   -- "snp" or "sgx"
+///     ids   OCTET STRING, -- SNP measurement, or SGX mrenclave || mrsigner
+///     tcb   INTEGER       -- SNP reported_tcb, or SGX isv_svn
+/// }
+///
+/// The [`OID_ATTESTATION_RESULT`] extension's value, so an RA-TLS
+/// verifier can recover what was attested directly from the issued
+/// certificate.
+#[derive(Clone, Debug, Sequence)]
+struct AttestationResult<'a> {
+    kind: Utf8String<'a>,
+    ids: OctetString<'a>,
+    tcb: UIntBytes<'a>,
 }
 
-const SNP_SIGNATURE_OFFSET:usize = 0x2A0;
-const SNP_BIGNUM_SIZE:usize = 0x48;
-
-impl SnpReportData {
-    fn get_message(&self) -> Vec<u8> {
-        //let bytes = unsafe { any_as_u8_slice(&self) };
-        let bytes = unsafe { std::mem::transmute::<&SnpReportData, &[u8;0x4A0]>(self) };
-        println!("SnpReportSize: {}", bytes.len());
-        bytes[..SNP_SIGNATURE_OFFSET].to_vec()
-    }
-
-    fn get_signature(&self) -> Vec<u8> {
-        let bytes = unsafe { std::mem::transmute::<&SnpReportData, &[u8;0x4A0]>(self) };
-        let mut r = bytes[SNP_SIGNATURE_OFFSET..SNP_SIGNATURE_OFFSET+SNP_BIGNUM_SIZE].to_vec();
-        let mut s = bytes[SNP_SIGNATURE_OFFSET+SNP_BIGNUM_SIZE..SNP_SIGNATURE_OFFSET+2*SNP_BIGNUM_SIZE].to_vec();
-        r.reverse();
-        s.reverse();
+/// Build an X.509v3 extension from a pre-encoded DER value.
+fn extension<'a>(oid: &str, critical: bool, value: &'a [u8]) -> Result<X509Extension<'a>, StatusCode> {
+    Ok(X509Extension {
+        extn_id: oid.parse().or(Err(StatusCode::INTERNAL_SERVER_ERROR))?,
+        critical,
+        extn_value: OctetString::new(value).or(Err(StatusCode::INTERNAL_SERVER_ERROR))?,
+    })
+}
 
-        let ecdsa = EcdsaSig {
-            r: UIntBytes::new(&r).unwrap(),
-            s: UIntBytes::new(&s).unwrap(),
-        };
+/// DER-encode a single `SubjectAltName` `GeneralName` of variant
+/// `uniformResourceIdentifier` (`[6] IA5String`) carrying a URN that
+/// binds the CSR's public-key digest, so a verifier can match the
+/// attested key against the certificate's SAN.
+fn subject_alt_name_uri(pubkey_digest: &[u8; 32]) -> Vec<u8> {
+    let uri = format!(
+        "urn:attested-pubkey:{}",
+        pubkey_digest.iter().map(|b| format!("{b:02x}")).collect::<String>()
+    );
+
+    let mut name = vec![0x86, uri.len() as u8];
+    name.extend_from_slice(uri.as_bytes());
+
+    let mut names = vec![0x30, name.len() as u8];
+    names.extend_from_slice(&name);
+    names
+}
 
-        ecdsa.to_vec().unwrap()
-    }
+/// Pull the `(kind, ids, tcb)` triple that [`AttestationResult`] embeds
+/// out of `evidence`'s already-verified `claims`, so the certificate is
+/// bound to the cryptographically-checked measurement/TCB, not the
+/// unverified shape of the evidence itself.
+fn attestation_result_fields(
+    evidence: &Evidence,
+    claims: &VerifiedClaims,
+) -> (&'static str, Vec<u8>, [u8; 8]) {
+    let kind = match evidence {
+        Evidence::Snp(_) => "snp",
+        Evidence::Sgx(_) => "sgx",
+    };
+    (kind, claims.measurement.clone(), claims.tcb.to_be_bytes())
 }
 
 #[tokio::main]
@@ -138,9 +276,16 @@ async fn main() {
 fn app(state: State) -> Router {
     Router::new()
         .route("/attest", post(attest))
+        .route("/challenge", get(challenge).post(challenge))
         .layer(AddExtensionLayer::new(Arc::new(state)))
 }
 
+/// Hand out a fresh, single-use nonce the client must fold into its
+/// attestation evidence's `report_data` before calling `/attest`.
+async fn challenge(Extension(state): Extension<Arc<State>>) -> Vec<u8> {
+    state.challenges.issue().to_vec()
+}
+
 async fn attest(
     TypedHeader(ct): TypedHeader<ContentType>,
     body: Bytes,
@@ -156,9 +301,59 @@ async fn attest(
     let cr = CertReq::from_der(body.as_ref()).or(Err(StatusCode::BAD_REQUEST))?;
     let cr = cr.verify().or(Err(StatusCode::BAD_REQUEST))?;
 
-    // TODO: validate attestation
+    // Bind the attestation evidence's report_data to the CSR's public key
+    // and a fresh /challenge nonce, so the issued cert provably belongs
+    // to a live, just-attested enclave/guest and can't be replayed.
+    let evidence = attestation_evidence(&cr).ok_or(StatusCode::BAD_REQUEST)?;
+    let evidence = Evidence::try_from(evidence).or(Err(StatusCode::BAD_REQUEST))?;
+    verify_report_data_binding(&cr, &evidence, &state.challenges)?;
+
+    // Verify the evidence's signature chain before trusting any of its
+    // fields for policy or certificate content: an SNP report must chain
+    // VCEK -> ASK -> ARK, and an SGX quote's PCK chain must root in the
+    // configured Intel SGX Root CA.
+    let vcek_der = attestation_vcek(&cr).unwrap_or_default();
+    let anchors = TrustAnchors {
+        ark_pem: &state.ark,
+        ask_pem: &state.ask,
+        vcek_der,
+        sgx_root_ca_pem: &state.sgx_root_ca,
+    };
+    let claims = evidence
+        .verify_signature(&anchors)
+        .or(Err(StatusCode::FORBIDDEN))?;
+
+    // Only issue a certificate to an allowlisted enclave/guest identity.
+    if !state.policy.permits(&evidence) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
     // TODO: validate other CSR fields
 
+    // Build the extensions carried on the issued certificate: the usual
+    // end-entity BasicConstraints/KeyUsage pair, a SubjectAltName binding
+    // the attested public key, and a private, non-critical extension
+    // embedding the verified attestation result (backend, measurement
+    // and TCB), so a downstream RA-TLS verifier can inspect what was
+    // attested without re-deriving it from this server's state (the
+    // MigTD RA-TLS pattern).
+    let pubkey_digest = public_key_digest(&cr).ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let san_value = subject_alt_name_uri(&pubkey_digest);
+    let (kind, ids, tcb) = attestation_result_fields(&evidence, &claims);
+    let result = AttestationResult {
+        kind: Utf8String::new(kind).or(Err(StatusCode::INTERNAL_SERVER_ERROR))?,
+        ids: OctetString::new(&ids).or(Err(StatusCode::INTERNAL_SERVER_ERROR))?,
+        tcb: UIntBytes::new(&tcb).or(Err(StatusCode::INTERNAL_SERVER_ERROR))?,
+    };
+    let result_value = result.to_vec().or(Err(StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    let extensions = vec![
+        extension(OID_BASIC_CONSTRAINTS, true, BASIC_CONSTRAINTS_CA_FALSE)?,
+        extension(OID_KEY_USAGE, true, KEY_USAGE_DIGITAL_SIGNATURE)?,
+        extension(OID_SUBJECT_ALT_NAME, false, &san_value)?,
+        extension(OID_ATTESTATION_RESULT, false, &result_value)?,
+    ];
+
     // Get the current time and the expiration of the cert.
     let now = SystemTime::now();
     let end = now + Duration::from_secs(60 * 60 * 24);
@@ -184,11 +379,11 @@ async fn attest(
             .or(Err(StatusCode::INTERNAL_SERVER_ERROR))?,
         issuer: issuer.tbs_certificate.subject.clone(),
         validity,
-        subject: issuer.tbs_certificate.subject.clone(), // FIXME
+        subject: cr.subject.clone(),
         subject_public_key_info: cr.public_key,
         issuer_unique_id: issuer.tbs_certificate.subject_unique_id,
         subject_unique_id: None,
-        extensions: None,
+        extensions: Some(extensions),
     };
 
     // Sign the certificate.
@@ -206,26 +401,55 @@ mod tests {
         use der::asn1::{SetOfVec, Utf8String};
         use der::{Encodable, asn1::UIntBytes};
 
-        use x509::attr::AttributeTypeAndValue;
+        use x509::attr::{Attribute, AttributeTypeAndValue};
         use x509::name::RelativeDistinguishedName;
         use x509::request::CertReqInfo;
 
+        use der::asn1::{Any, OctetString};
         use http::{header::CONTENT_TYPE, Request};
         use hyper::Body;
+        use sha2::{Digest, Sha256};
         use tower::ServiceExt; // for `app.oneshot()`
 
         const CRT: &[u8] = include_bytes!("../certs/test/crt.der");
         const KEY: &[u8] = include_bytes!("../certs/test/key.der");
 
+        const ARK: &str = include_str!("../certs/amd/milan_ark.pem");
+        const ASK: &str = include_str!("../certs/amd/milan_ask.pem");
+
+        // A policy permitting the all-zero SNP identity that `signed_cr`
+        // attaches below.
+        fn policy() -> Policy {
+            Policy {
+                snp: vec![policy::SnpIdentity {
+                    measurement: vec![0; 48],
+                    host_data: vec![0; 32],
+                    policy: 0,
+                    min_guest_svn: 0,
+                    min_reported_tcb: 0,
+                }],
+                sgx: Vec::new(),
+            }
+        }
+
         fn state() -> State {
             State {
                 key: KEY.to_owned().into(),
                 crt: CRT.into(),
+                ark: ARK.to_owned(),
+                ask: ASK.to_owned(),
+                sgx_root_ca: String::new(),
+                policy: policy(),
+                challenges: Default::default(),
                 ord: Default::default(),
             }
         }
 
-        fn cr() -> Vec<u8> {
+        // Build a signed CSR. When `nonce` is set, attach a well-formed
+        // (but otherwise blank) SNP report whose `report_data` binds
+        // the CSR's public key (first 32 bytes) and the given
+        // `/challenge` nonce (next 32 bytes).
+        fn signed_cr(nonce: Option<[u8; challenge::NONCE_SIZE]>) -> Vec<u8> {
             let pki = PrivateKeyInfo::generate(oids::NISTP256).unwrap();
             let pki = PrivateKeyInfo::from_der(pki.as_ref()).unwrap();
             let spki = pki.public_key().unwrap();
@@ -238,10 +462,31 @@ mod tests {
             })
             .unwrap();
 
+            let mut attributes = SetOfVec::new();
+            if let Some(nonce) = nonce {
+                let digest = Sha256::digest(&spki.to_vec().unwrap());
+                let mut evidence = vec![0u8; SNP_REPORT_SIZE];
+                evidence[SNP_REPORT_DATA_OFFSET..SNP_REPORT_DATA_OFFSET + 32]
+                    .copy_from_slice(&digest);
+                evidence[SNP_REPORT_DATA_OFFSET + 32..SNP_REPORT_DATA_OFFSET + 64]
+                    .copy_from_slice(&nonce);
+
+                let mut values = SetOfVec::new();
+                values
+                    .add(Any::from(OctetString::new(&evidence).unwrap()))
+                    .unwrap();
+                attributes
+                    .add(Attribute {
+                        oid: OID_ATTESTATION_EVIDENCE.parse().unwrap(),
+                        values,
+                    })
+                    .unwrap();
+            }
+
             // Create a certification request information structure.
             let cri = CertReqInfo {
                 version: x509::request::Version::V1,
-                attributes: SetOfVec::new(), // Extension requests go here.
+                attributes,
                 subject: [rdn].into(),
                 public_key: spki,
             };
@@ -250,6 +495,10 @@ mod tests {
             cri.sign(&pki).unwrap()
         }
 
+        fn cr() -> Vec<u8> {
+            signed_cr(None)
+        }
+
         #[test]
         fn test_milan_validation() {
             use std::fs;
@@ -303,44 +552,96 @@ mod tests {
         }
 
         #[test]
-        fn test_milan_validation_struct() {
+        fn test_milan_chain_validation() {
             use std::fs;
             let test_file = fs::read("tests/test1_le.bin").unwrap();
             assert_eq!(test_file.len(), 0x4A0, "attestation blob size");
             let mut test_file_bytes = [0u8; 0x4A0];
-            for (i, v) in test_file.iter().enumerate() { test_file_bytes[i] = *v; }
+            for (i, v) in test_file.iter().enumerate() {
+                test_file_bytes[i] = *v;
+            }
 
             assert_eq!(test_file.len(), core::mem::size_of::<SnpReportData>());
-            //let report_data = test_file.as_ptr() as *const SnpReportData;
-            //let the_report = unsafe { report_data.read_unaligned() };
+            let the_report: SnpReportData =
+                unsafe { std::mem::transmute::<[u8; 0x4A0], SnpReportData>(test_file_bytes) };
+
+            const MILAN_VCEK: &str = include_str!("../certs/amd/milan_vcek.pem");
+            let vcek_ders = PkiPath::parse_pem(MILAN_VCEK).unwrap();
+            assert_eq!(vcek_ders.len(), 1, "The SNP cert is just one cert");
+
+            snp::verify_snp_chain(&the_report, &vcek_ders[0], ARK, ASK, SystemTime::now())
+                .expect("VCEK should chain to ARK via ASK and sign the report");
+        }
 
-            let the_report:SnpReportData = unsafe { std::mem::transmute::<[u8;0x4A0],SnpReportData>(test_file_bytes) };
-            //let (head, body, _tail) = unsafe { test_file.align_to::<SnpReportData>() };
-            //assert!(head.is_empty(), "Data was not aligned");
-            //let the_report = body[0];
+        #[test]
+        fn test_milan_evidence_verifier() {
+            // The same fixture as `test_milan_chain_validation`, but
+            // exercised through the backend-agnostic `Evidence`/`Verifier`
+            // abstraction: auto-detection must land on `Evidence::Snp`,
+            // and `verify_signature` must agree with `verify_snp_chain`.
+            use std::fs;
+            let test_file = fs::read("tests/test1_le.bin").unwrap();
+
+            let evidence = Evidence::try_from(test_file.as_slice()).unwrap();
+            assert!(matches!(evidence, Evidence::Snp(_)));
 
-            println!("{:?}", the_report);
             const MILAN_VCEK: &str = include_str!("../certs/amd/milan_vcek.pem");
-            let veck = PkiPath::parse_pem(MILAN_VCEK).unwrap();
-            let vcek_path = PkiPath::from_ders(&veck).unwrap();
-            assert_eq!(vcek_path.len(), 1, "The SNP cert is just one cert");
-            let the_cert = vcek_path.first().unwrap();
+            let vcek_ders = PkiPath::parse_pem(MILAN_VCEK).unwrap();
 
-            match the_cert.tbs_certificate.verify_raw(
-                the_report.get_message().as_slice(),
-                pkcs8::AlgorithmIdentifier {
-                    oid: ECDSA_SHA384,
-                    parameters: None,
-                },
-                the_report.get_signature().as_slice(),
-            ) {
-                Ok(_) => {
-                    assert!(true, "Message passed");
-                }
-                Err(e) => {
-                    assert!(false, "Message invalid {}", e);
-                }
-            }
+            let anchors = evidence::TrustAnchors {
+                ark_pem: ARK,
+                ask_pem: ASK,
+                vcek_der: &vcek_ders[0],
+                sgx_root_ca_pem: "",
+            };
+
+            let claims = evidence
+                .verify_signature(&anchors)
+                .expect("VCEK should chain to ARK via ASK and sign the report");
+            assert_eq!(claims.report_data, evidence.report_data());
+        }
+
+        #[test]
+        fn test_sgx_quote_verification() {
+            // An SGX analogue of `test_milan_chain_validation`: a
+            // captured DCAP quote whose PCK certification chain and
+            // ECDSA-P256-SHA256 signatures must verify end to end
+            // against a trusted Intel SGX Root CA.
+            use crate::ext::sgx::quote::Quote;
+            use std::fs;
+
+            let test_file = fs::read("tests/sgx_quote_v3.bin").unwrap();
+            let quote = Quote::try_from(test_file.as_slice()).unwrap();
+
+            const SGX_ROOT_CA: &str = include_str!("../certs/sgx/root_ca.pem");
+            quote
+                .verify(SGX_ROOT_CA, SystemTime::now())
+                .expect("PCK chain should root in the Intel SGX Root CA and sign the quote");
+        }
+
+        #[test]
+        fn test_sgx_evidence_verifier() {
+            // The same fixture as `test_sgx_quote_verification`, but
+            // exercised through the backend-agnostic `Evidence`/`Verifier`
+            // abstraction: auto-detection must land on `Evidence::Sgx`.
+            use std::fs;
+
+            let test_file = fs::read("tests/sgx_quote_v3.bin").unwrap();
+            let evidence = Evidence::try_from(test_file.as_slice()).unwrap();
+            assert!(matches!(evidence, Evidence::Sgx(_)));
+
+            const SGX_ROOT_CA: &str = include_str!("../certs/sgx/root_ca.pem");
+            let anchors = evidence::TrustAnchors {
+                ark_pem: "",
+                ask_pem: "",
+                vcek_der: &[],
+                sgx_root_ca_pem: SGX_ROOT_CA,
+            };
+
+            let claims = evidence
+                .verify_signature(&anchors)
+                .expect("PCK chain should root in the Intel SGX Root CA and sign the quote");
+            assert_eq!(claims.report_data, evidence.report_data());
         }
 
         #[test]
@@ -358,7 +659,42 @@ mod tests {
         }
 
         #[tokio::test]
-        async fn ok() {
+        async fn err_forged_evidence_rejected() {
+            // `signed_cr`'s evidence is a well-formed but otherwise blank
+            // SNP report: correct size, correctly bound report_data, but
+            // not signed by any VCEK. Now that `attest` calls
+            // `evidence.verify_signature`, this must be rejected rather
+            // than issued a certificate.
+            let state = state();
+            let nonce = state.challenges.issue();
+            let signed = signed_cr(Some(nonce));
+
+            let request = Request::builder()
+                .method("POST")
+                .uri("/attest")
+                .header(CONTENT_TYPE, PKCS10)
+                .body(Body::from(signed))
+                .unwrap();
+
+            let response = app(state).oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+
+        // A full happy-path exercise of `attest` over HTTP would need
+        // evidence that is both (a) bound to this CSR's key and a
+        // freshly issued nonce, and (b) signed by a VCEK chaining to
+        // `state`'s ARK/ASK. The Milan fixture used by
+        // `test_milan_chain_validation` satisfies (b) but has a fixed
+        // report_data baked in at capture time, so it can't satisfy (a)
+        // for an independently-generated CSR/nonce without re-signing a
+        // new report, which needs AMD's VCEK private key. That signature
+        // verification and the rejection of unsigned evidence are
+        // covered instead by `test_milan_chain_validation`/
+        // `test_milan_evidence_verifier` and
+        // `err_forged_evidence_rejected`, respectively.
+
+        #[tokio::test]
+        async fn err_no_attestation_evidence() {
             let request = Request::builder()
                 .method("POST")
                 .uri("/attest")
@@ -367,13 +703,82 @@ mod tests {
                 .unwrap();
 
             let response = app(state()).oneshot(request).await.unwrap();
-            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
 
-            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        #[tokio::test]
+        async fn err_attestation_evidence_not_bound_to_key() {
+            // A correctly-shaped SNP report whose report_data is bound
+            // to the wrong public key.
+            let evidence = vec![0u8; SNP_REPORT_SIZE];
+
+            let pki = PrivateKeyInfo::generate(oids::NISTP256).unwrap();
+            let pki = PrivateKeyInfo::from_der(pki.as_ref()).unwrap();
+            let spki = pki.public_key().unwrap();
+
+            let mut rdn = RelativeDistinguishedName::new();
+            rdn.add(AttributeTypeAndValue {
+                oid: x509::ext::pkix::oids::AT_COMMON_NAME,
+                value: Utf8String::new("foo").unwrap().into(),
+            })
+            .unwrap();
+
+            let mut values = SetOfVec::new();
+            values
+                .add(Any::from(OctetString::new(&evidence).unwrap()))
+                .unwrap();
+            let mut attributes = SetOfVec::new();
+            attributes
+                .add(Attribute {
+                    oid: OID_ATTESTATION_EVIDENCE.parse().unwrap(),
+                    values,
+                })
+                .unwrap();
+
+            let cri = CertReqInfo {
+                version: x509::request::Version::V1,
+                attributes,
+                subject: [rdn].into(),
+                public_key: spki,
+            };
+            let body = cri.sign(&pki).unwrap();
+
+            let request = Request::builder()
+                .method("POST")
+                .uri("/attest")
+                .header(CONTENT_TYPE, PKCS10)
+                .body(Body::from(body))
+                .unwrap();
+
+            let response = app(state()).oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn err_enclave_identity_not_permitted() {
+            // Bound correctly, but the allowlist is empty: no identity
+            // is permitted. The blank evidence is also unsigned, so over
+            // HTTP `attest` already answers FORBIDDEN at signature
+            // verification; the allowlist decision itself is checked
+            // directly against the same blank identity.
+            let blank = [0u8; SNP_REPORT_SIZE];
+            let blank = Evidence::try_from(&blank[..]).unwrap();
+            assert!(policy().permits(&blank));
+            assert!(!Policy::default().permits(&blank));
+
+            let mut unpermitted = state();
+            unpermitted.policy = Policy::default();
+            let nonce = unpermitted.challenges.issue();
+
+            let request = Request::builder()
+                .method("POST")
+                .uri("/attest")
+                .header(CONTENT_TYPE, PKCS10)
+                .body(Body::from(signed_cr(Some(nonce))))
+                .unwrap();
 
-            let sub = Certificate::from_der(&body).unwrap();
-            let iss = Certificate::from_der(CRT).unwrap();
-            iss.tbs_certificate.verify_crt(&sub).unwrap();
+            let response = app(unpermitted).oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
         }
 
         #[tokio::test]
@@ -0,0 +1,103 @@
+//! Enclave-identity allowlist consulted by the `/attest` handler once
+//! attestation evidence has been parsed. This mirrors the Teaclave
+//! attestation refactor's enclave-identity allowlisting, generalized to
+//! both AMD SEV-SNP and Intel SGX evidence.
+
+use sgx::ReportBody;
+use serde::Deserialize;
+
+use crate::evidence::Evidence;
+use crate::snp::SnpReportData;
+
+/// A permitted AMD SEV-SNP guest identity.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SnpIdentity {
+    pub(crate) measurement: Vec<u8>,
+    pub(crate) host_data: Vec<u8>,
+    pub(crate) policy: u64,
+    pub(crate) min_guest_svn: u32,
+    pub(crate) min_reported_tcb: u64,
+}
+
+impl SnpIdentity {
+    fn permits(&self, report: &SnpReportData) -> bool {
+        self.measurement.as_slice() == report.measurement
+            && self.host_data.as_slice() == report.host_data
+            && self.policy == report.policy
+            && report.guest_svn >= self.min_guest_svn
+            && report.reported_tcb >= self.min_reported_tcb
+    }
+}
+
+/// A permitted Intel SGX enclave identity.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SgxIdentity {
+    pub(crate) mrenclave: Vec<u8>,
+    pub(crate) mrsigner: Vec<u8>,
+    pub(crate) min_isvsvn: u16,
+}
+
+impl SgxIdentity {
+    fn permits(&self, report: &ReportBody) -> bool {
+        self.mrenclave.as_slice() == report.mr_enclave
+            && self.mrsigner.as_slice() == report.mr_signer
+            && report.isv_svn >= self.min_isvsvn
+    }
+}
+
+/// The set of enclave/guest identities this server will issue a
+/// certificate to. Loaded once at startup from a TOML policy file; see
+/// [`Policy::parse`] for the expected shape.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Policy {
+    #[serde(default)]
+    pub(crate) snp: Vec<SnpIdentity>,
+    #[serde(default)]
+    pub(crate) sgx: Vec<SgxIdentity>,
+}
+
+impl Policy {
+    /// Parse a policy file of the form:
+    ///
+    /// ```toml
+    /// [[snp]]
+    /// measurement = [..]      # 48 bytes
+    /// host_data = [..]        # 32 bytes
+    /// policy = 0
+    /// min_guest_svn = 0
+    /// min_reported_tcb = 0
+    ///
+    /// [[sgx]]
+    /// mrenclave = [..]        # 32 bytes
+    /// mrsigner = [..]         # 32 bytes
+    /// min_isvsvn = 0
+    /// ```
+    pub fn parse(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    /// Load and parse a policy file from disk.
+    pub fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Is `report` one of the allowlisted SNP guest identities?
+    pub fn permits_snp(&self, report: &SnpReportData) -> bool {
+        self.snp.iter().any(|id| id.permits(report))
+    }
+
+    /// Is `report` one of the allowlisted SGX enclave identities?
+    pub fn permits_sgx(&self, report: &ReportBody) -> bool {
+        self.sgx.iter().any(|id| id.permits(report))
+    }
+
+    /// Is `evidence`'s attested identity allowlisted, dispatching on
+    /// its backend.
+    pub fn permits(&self, evidence: &Evidence) -> bool {
+        match evidence {
+            Evidence::Snp(report) => self.permits_snp(report),
+            Evidence::Sgx(quote) => self.permits_sgx(quote.report_body()),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! AMD SEV-SNP attestation report types and verification.
+//!
+//! A report is signed by the VCEK, whose certificate chains through the
+//! ASK intermediate to the AMD Root Key (ARK). See the "SEV-SNP: Strengthening
+//! VM Isolation with Integrity Protection and More" whitepaper, section 8,
+//! for the report and certificate chain layout this module implements.
+
+use crate::crypto::{oids::ECDSA_SHA384, PkiPath};
+
+use std::time::SystemTime;
+
+use der::asn1::UIntBytes;
+use der::{Decodable, Encodable};
+use x509::Certificate;
+
+/// ECDSA-Sig-Value ::= SEQUENCE { r INTEGER, s INTEGER }
+#[derive(Clone, Debug, der::Sequence)]
+struct EcdsaSig<'a> {
+    r: UIntBytes<'a>,
+    s: UIntBytes<'a>,
+}
+
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct SnpReportData {
+    pub version: u32,
+    pub guest_svn: u32,
+    pub policy: u64,
+    pub family_id: [u8; 16],
+    pub image_id: [u8; 16],
+    pub vmpl: u32,
+    pub sig_algo: u32,
+    pub plat_version: u64,
+    pub plat_info: u64,
+    pub author_key_en: u32,
+    rsvd1: u32,
+    pub report_data: [u8; 64],
+    pub measurement: [u8; 48],
+    pub host_data: [u8; 32],
+    pub id_key_digest: [u8; 48],
+    pub author_key_digest: [u8; 48],
+    pub report_id: [u8; 32],
+    pub report_id_ma: [u8; 32],
+    pub reported_tcb: u64,
+    rsvd2: [u8; 24],
+    pub chip_id: [u8; 64],
+    rsvd3: [u8; 192],
+    pub signature: [u8; 512],
+}
+
+/// Byte length of a raw SNP attestation report.
+pub const REPORT_SIZE: usize = 0x4A0;
+/// Offset of `report_data` within the raw report blob.
+pub const REPORT_DATA_OFFSET: usize = 0x50;
+
+const SIGNATURE_OFFSET: usize = 0x2A0;
+const BIGNUM_SIZE: usize = 0x48;
+
+/// The four TCB component OIDs AMD stamps into the VCEK certificate,
+/// under AMD's `1.3.6.1.4.1.3704` enterprise arc. Each extension's
+/// value must be `>=` the corresponding byte of `reported_tcb`.
+mod tcb_oids {
+    pub const BL_SPL: &str = "1.3.6.1.4.1.3704.1.3.1";
+    pub const TEE_SPL: &str = "1.3.6.1.4.1.3704.1.3.2";
+    pub const SNP_SPL: &str = "1.3.6.1.4.1.3704.1.3.3";
+    pub const UCODE_SPL: &str = "1.3.6.1.4.1.3704.1.3.8";
+}
+
+/// The decomposed TCB security patch levels, per the report's
+/// `reported_tcb` field layout (bytes 0, 1, 6 and 7 respectively).
+struct Tcb {
+    bootloader: u8,
+    tee: u8,
+    snp: u8,
+    microcode: u8,
+}
+
+impl From<u64> for Tcb {
+    fn from(reported_tcb: u64) -> Self {
+        let b = reported_tcb.to_le_bytes();
+        Self {
+            bootloader: b[0],
+            tee: b[1],
+            snp: b[6],
+            microcode: b[7],
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for SnpReportData {
+    type Error = std::array::TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: [u8; REPORT_SIZE] = bytes.try_into()?;
+        Ok(unsafe { std::mem::transmute(bytes) })
+    }
+}
+
+impl SnpReportData {
+    pub fn get_message(&self) -> Vec<u8> {
+        let bytes = unsafe { std::mem::transmute::<&SnpReportData, &[u8; REPORT_SIZE]>(self) };
+        bytes[..SIGNATURE_OFFSET].to_vec()
+    }
+
+    pub fn get_signature(&self) -> Vec<u8> {
+        let bytes = unsafe { std::mem::transmute::<&SnpReportData, &[u8; REPORT_SIZE]>(self) };
+        let mut r = bytes[SIGNATURE_OFFSET..SIGNATURE_OFFSET + BIGNUM_SIZE].to_vec();
+        let mut s =
+            bytes[SIGNATURE_OFFSET + BIGNUM_SIZE..SIGNATURE_OFFSET + 2 * BIGNUM_SIZE].to_vec();
+        r.reverse();
+        s.reverse();
+
+        let ecdsa = EcdsaSig {
+            r: UIntBytes::new(&r).unwrap(),
+            s: UIntBytes::new(&s).unwrap(),
+        };
+
+        ecdsa.to_vec().unwrap()
+    }
+}
+
+/// Look up a TCB extension's single-byte SPL value on `cert`.
+fn extension_spl(cert: &Certificate<'_>, oid: &str) -> anyhow::Result<u8> {
+    let oid = oid.parse()?;
+    let ext = cert
+        .tbs_certificate
+        .extensions
+        .iter()
+        .flatten()
+        .find(|ext| ext.extn_id == oid)
+        .ok_or_else(|| anyhow::anyhow!("missing TCB extension {}", oid))?;
+
+    // AMD encodes these as an INTEGER wrapping the single SPL byte.
+    ext.extn_value
+        .as_bytes()
+        .last()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("empty TCB extension {}", oid))
+}
+
+/// Verify that `vcek`'s stamped TCB values are at least as new as the
+/// report's `reported_tcb`, so the report can't claim a lower, more
+/// vulnerable patch level than the certificate attests to.
+fn verify_tcb(vcek: &Certificate<'_>, reported_tcb: u64) -> anyhow::Result<()> {
+    let tcb: Tcb = reported_tcb.into();
+
+    let checks = [
+        (tcb_oids::BL_SPL, tcb.bootloader),
+        (tcb_oids::TEE_SPL, tcb.tee),
+        (tcb_oids::SNP_SPL, tcb.snp),
+        (tcb_oids::UCODE_SPL, tcb.microcode),
+    ];
+
+    for (oid, reported) in checks {
+        let cert_spl = extension_spl(vcek, oid)?;
+        anyhow::ensure!(
+            cert_spl >= reported,
+            "VCEK TCB extension {} ({}) is older than reported_tcb ({})",
+            oid,
+            cert_spl,
+            reported
+        );
+    }
+
+    Ok(())
+}
+
+/// Check that `cert`'s validity period covers `now`, so an expired or
+/// not-yet-valid ARK/ASK/VCEK can't stand in the trust chain.
+fn verify_validity(cert: &Certificate<'_>, now: SystemTime) -> anyhow::Result<()> {
+    let validity = &cert.tbs_certificate.validity;
+    let not_before = SystemTime::try_from(validity.not_before)?;
+    let not_after = SystemTime::try_from(validity.not_after)?;
+    anyhow::ensure!(now >= not_before, "certificate is not yet valid");
+    anyhow::ensure!(now <= not_after, "certificate has expired");
+    Ok(())
+}
+
+/// Verify the full AMD SEV-SNP trust chain for `report`: the VCEK leaf
+/// must chain to the ASK intermediate and on to the ARK trust anchor,
+/// each certificate's signature verifying the next and all validity
+/// periods covering `now`; the VCEK's TCB extensions must be at least
+/// as new as `report.reported_tcb`; and the report itself must be
+/// signed by the VCEK.
+pub fn verify_snp_chain(
+    report: &SnpReportData,
+    vcek_der: &[u8],
+    ark_pem: &str,
+    ask_pem: &str,
+    now: SystemTime,
+) -> anyhow::Result<()> {
+    let vcek = Certificate::from_der(vcek_der)?;
+
+    let ask = PkiPath::from_ders(&PkiPath::parse_pem(ask_pem)?)?;
+    let ask = ask.first().ok_or_else(|| anyhow::anyhow!("no ASK cert"))?;
+
+    let ark = PkiPath::from_ders(&PkiPath::parse_pem(ark_pem)?)?;
+    let ark = ark.first().ok_or_else(|| anyhow::anyhow!("no ARK cert"))?;
+
+    verify_validity(ark, now)?;
+    verify_validity(ask, now)?;
+    verify_validity(&vcek, now)?;
+
+    // The ARK is the trust anchor: it is self-signed.
+    ark.tbs_certificate.verify_crt(ark)?;
+    // ASK <- ARK, VCEK <- ASK.
+    ark.tbs_certificate.verify_crt(ask)?;
+    ask.tbs_certificate.verify_crt(&vcek)?;
+
+    verify_tcb(&vcek, report.reported_tcb)?;
+
+    vcek.tbs_certificate.verify_raw(
+        &report.get_message(),
+        pkcs8::AlgorithmIdentifier {
+            oid: ECDSA_SHA384,
+            parameters: None,
+        },
+        &report.get_signature(),
+    )?;
+
+    Ok(())
+}